@@ -2,8 +2,9 @@ use std::collections::{HashMap, HashSet};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
-use anyhow::{Error, Result};
+use anyhow::{bail, Context, Error, Result};
 use clap::{Parser, ValueEnum};
 use frecenfile::analyze_repo;
 use grep::{
@@ -11,17 +12,51 @@ use grep::{
     regex::RegexMatcher,
     searcher::{BinaryDetection, MmapChoice, SearcherBuilder, sinks::UTF8},
 };
+use humantime::{parse_duration, parse_rfc3339_weak};
+use ignore::overrides::{Override, OverrideBuilder};
+use ignore::types::{Types, TypesBuilder};
 use ignore::WalkBuilder;
+use lscolors::LsColors;
 use termcolor::{ColorChoice, ColorSpec, StandardStream, WriteColor};
 
+mod verbs;
+use verbs::VerbManager;
+
 /// Search frecently edited code in a Git repository
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
 struct Args {
     /// Regular Expression pattern
-    #[arg()]
+    #[arg(
+        required_unless_present_any = ["type_list", "list_verbs"],
+        default_value = ""
+    )]
     pattern: String,
 
+    /// Files or directories to search (defaults to the current directory)
+    #[arg()]
+    paths: Vec<PathBuf>,
+
+    /// Include or exclude files matching this glob (prefix with `!` to exclude)
+    #[arg(short = 'g', long = "glob")]
+    glob: Vec<String>,
+
+    /// Like --glob, but case insensitive
+    #[arg(long = "iglob")]
+    iglob: Vec<String>,
+
+    /// Only search files matching this type (e.g. `rust`, `py`)
+    #[arg(short = 't', long = "type")]
+    type_matched: Vec<String>,
+
+    /// Exclude files matching this type
+    #[arg(short = 'T', long = "type-not")]
+    type_not: Vec<String>,
+
+    /// List the known file types and their globs, then exit
+    #[arg(long = "type-list")]
+    type_list: bool,
+
     /// Case-insensitive regex matching
     #[arg(short = 'i', long = "ignore-case")]
     ignore_case: bool,
@@ -41,6 +76,30 @@ struct Args {
     /// Controls when to use color
     #[arg(long, value_enum, default_value = "auto")]
     color: Color,
+
+    /// Don't colorize file paths according to LS_COLORS
+    #[arg(long)]
+    no_filename_color: bool,
+
+    /// Only include files whose size matches, e.g. `+1M` or `-500k`
+    #[arg(long)]
+    size: Option<String>,
+
+    /// Only include files modified more recently than this date or duration ago, e.g. `2weeks`
+    #[arg(long)]
+    newer: Option<String>,
+
+    /// Only include files modified before this date or duration ago
+    #[arg(long)]
+    older: Option<String>,
+
+    /// Render each match through the named --format verb template instead of the default layout
+    #[arg(long = "format")]
+    format: Option<String>,
+
+    /// List the available --format verb templates and their source, then exit
+    #[arg(long = "list-verbs")]
+    list_verbs: bool,
 }
 
 #[derive(Copy, Clone, Debug, ValueEnum, PartialEq)]
@@ -64,13 +123,327 @@ fn normalize_repo_path(path: &Path) -> &Path {
     path.strip_prefix(".").unwrap_or(path)
 }
 
+/// Skip the body of a `\x`/`\u`/`\U`/`\p`/`\P`-style escape token: a `{...}`
+/// braced form if present, otherwise exactly `fixed_digits` characters
+/// (2 for `\x`, 4 for `\u`, 8 for `\U`, 1 for the single-letter `\p`/`\P`
+/// class shorthand).
+fn skip_escape_body(chars: &mut std::str::Chars, fixed_digits: usize) {
+    if chars.clone().next() == Some('{') {
+        chars.next();
+        for inner in chars.by_ref() {
+            if inner == '}' {
+                break;
+            }
+        }
+    } else {
+        for _ in 0..fixed_digits {
+            chars.next();
+        }
+    }
+}
+
+/// Whether `pattern` contains a literal uppercase letter, for smart-case
+/// purposes. Uppercase letters that only appear inside a regex escape token
+/// (`\B`, `\A`, `\x1B`, `\p{Lu}`, `\u{1F600}`, ...) don't count, since they
+/// don't express a literal uppercase character to match against.
+fn pattern_has_uppercase_char(pattern: &str) -> bool {
+    let mut chars = pattern.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('x') => skip_escape_body(&mut chars, 2),
+                Some('u') => skip_escape_body(&mut chars, 4),
+                Some('U') => skip_escape_body(&mut chars, 8),
+                Some('p') | Some('P') => skip_escape_body(&mut chars, 1),
+                Some(_) | None => {}
+            }
+            continue;
+        }
+
+        if c.is_uppercase() {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Longest leading directory component of a glob that contains no wildcard
+/// characters, e.g. `src/**/*.rs` narrows to `src`. Returns `None` when the
+/// glob has no literal prefix (starts with a wildcard, has no `/`, or is
+/// negated).
+fn literal_prefix_dir(glob: &str) -> Option<PathBuf> {
+    if glob.starts_with('!') {
+        return None;
+    }
+    let mut prefix = PathBuf::new();
+
+    // The last component names the files themselves, never a directory.
+    let mut components: Vec<&str> = glob.split('/').collect();
+    components.pop();
+    for component in components {
+        if component.is_empty() || component.contains(['*', '?', '[', '{']) {
+            break;
+        }
+        prefix.push(component);
+    }
+
+    if prefix.as_os_str().is_empty() {
+        None
+    } else {
+        Some(prefix)
+    }
+}
+
+/// Determine the search roots: explicit `paths` if given, otherwise `.`,
+/// narrowed to each include glob's literal prefix directory when possible so
+/// the walk doesn't descend into unrelated subtrees. Globs with prefixes
+/// under different trees (e.g. `src/**/*.rs` and `tests/**/*.rs`) each become
+/// their own walk root, rather than only the first one narrowing the walk
+/// and silently dropping matches under the others. An include glob with no
+/// literal prefix, or any include `--iglob` (whose prefix matches in any
+/// case), keeps the walk at `.`.
+fn search_roots(args: &Args) -> Vec<PathBuf> {
+    if !args.paths.is_empty() {
+        return args.paths.clone();
+    }
+
+    if args.iglob.iter().any(|pattern| !pattern.starts_with('!')) {
+        return vec![PathBuf::from(".")];
+    }
+
+    let mut narrowed: Vec<PathBuf> = Vec::new();
+    for pattern in args.glob.iter().filter(|pattern| !pattern.starts_with('!')) {
+        match literal_prefix_dir(pattern) {
+            Some(dir) => {
+                if !narrowed.contains(&dir) {
+                    narrowed.push(dir);
+                }
+            }
+            None => return vec![PathBuf::from(".")],
+        }
+    }
+
+    if narrowed.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        narrowed
+    }
+}
+
+/// Build the `ignore::overrides::Override` applying `--glob`/`--iglob`.
+/// Always relative to the current directory, since that's what the glob
+/// patterns themselves are written against (e.g. `src/**/*.rs`) — the walk
+/// root passed to `WalkBuilder` may be narrowed to a glob's literal prefix
+/// (see `search_roots`), but the override must not be narrowed again on top
+/// of that or the glob's now-redundant prefix stops matching.
+fn build_overrides(args: &Args) -> Result<Override> {
+    let mut builder = OverrideBuilder::new(".");
+
+    for pattern in &args.glob {
+        builder.add(pattern)?;
+    }
+
+    for pattern in &args.iglob {
+        builder.case_insensitive(true)?;
+        builder.add(pattern)?;
+        builder.case_insensitive(false)?;
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Build the `ignore::types::Types` applying `-t/--type` and `-T/--type-not`
+/// on top of the crate's built-in language definitions.
+fn build_types(args: &Args) -> Result<Types> {
+    let mut builder = TypesBuilder::new();
+    builder.add_defaults();
+
+    for type_name in &args.type_matched {
+        builder.select(type_name);
+    }
+    for type_name in &args.type_not {
+        builder.negate(type_name);
+    }
+
+    Ok(builder.build()?)
+}
+
+/// Print the known `-t/--type` names and the globs they expand to, one per
+/// line, in the same `name: glob1, glob2, ...` format as `rg --type-list`.
+fn print_type_list() -> Result<()> {
+    let mut builder = TypesBuilder::new();
+    builder.add_defaults();
+    let types = builder.build()?;
+
+    for def in types.definitions() {
+        println!("{}: {}", def.name(), def.globs().join(", "));
+    }
+
+    Ok(())
+}
+
+/// Lower/upper byte bounds parsed from `--size`.
+#[derive(Debug, Clone, Copy, Default)]
+struct SizeFilter {
+    min: Option<u64>,
+    max: Option<u64>,
+}
+
+/// Parse a `--size` spec like `+1M` or `-500k` into a `SizeFilter`.
+fn parse_size_filter(spec: &str) -> Result<SizeFilter> {
+    let (sign, rest) = spec
+        .strip_prefix('+')
+        .map(|rest| ('+', rest))
+        .or_else(|| spec.strip_prefix('-').map(|rest| ('-', rest)))
+        .with_context(|| format!("size filter must start with + or -: {spec}"))?;
+
+    let split_at = rest
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(rest.len());
+    let (digits, unit) = rest.split_at(split_at);
+    let number: u64 = digits
+        .parse()
+        .with_context(|| format!("invalid size: {spec}"))?;
+
+    let multiplier = match unit.to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" => 1_000,
+        "ki" => 1_024,
+        "m" => 1_000_000,
+        "mi" => 1_048_576,
+        "g" => 1_000_000_000,
+        "gi" => 1_073_741_824,
+        other => bail!("unknown size unit '{other}' in {spec}"),
+    };
+    let bytes = number * multiplier;
+
+    Ok(match sign {
+        '+' => SizeFilter {
+            min: Some(bytes),
+            max: None,
+        },
+        _ => SizeFilter {
+            min: None,
+            max: Some(bytes),
+        },
+    })
+}
+
+/// Parse a `--newer`/`--older` spec, accepting both RFC3339-ish dates
+/// (`2024-01-01`) and relative durations (`2weeks`, `3days`), the latter
+/// being measured back from now.
+fn parse_time_threshold(spec: &str) -> Result<SystemTime> {
+    if let Ok(duration) = parse_duration(spec) {
+        return Ok(SystemTime::now() - duration);
+    }
+    if let Ok(time) = parse_rfc3339_weak(spec) {
+        return Ok(time);
+    }
+
+    // `parse_rfc3339_weak` rejects a bare date with no time component;
+    // assume midnight so a plain `2024-01-01` is accepted too.
+    let with_midnight = format!("{spec} 00:00:00");
+    parse_rfc3339_weak(&with_midnight)
+        .with_context(|| format!("invalid date or duration: {spec}"))
+}
+
+/// Combined `--size`/`--newer`/`--older` constraints applied to each
+/// candidate file before it's searched.
+#[derive(Debug, Clone, Copy, Default)]
+struct FileFilters {
+    size: SizeFilter,
+    newer_than: Option<SystemTime>,
+    older_than: Option<SystemTime>,
+}
+
+impl FileFilters {
+    fn is_empty(&self) -> bool {
+        self.size.min.is_none()
+            && self.size.max.is_none()
+            && self.newer_than.is_none()
+            && self.older_than.is_none()
+    }
+
+    /// Whether `path` satisfies every configured constraint. Files whose
+    /// metadata can't be read are let through rather than silently dropped.
+    fn matches(&self, path: &Path) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        let Ok(metadata) = std::fs::metadata(path) else {
+            return true;
+        };
+
+        if let Some(min) = self.size.min {
+            if metadata.len() < min {
+                return false;
+            }
+        }
+        if let Some(max) = self.size.max {
+            if metadata.len() > max {
+                return false;
+            }
+        }
+
+        if let Ok(modified) = metadata.modified() {
+            if let Some(threshold) = self.newer_than {
+                if modified < threshold {
+                    return false;
+                }
+            }
+            if let Some(threshold) = self.older_than {
+                if modified > threshold {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+}
+
+/// Build the `FileFilters` from `--size`/`--newer`/`--older`.
+fn build_file_filters(args: &Args) -> Result<FileFilters> {
+    Ok(FileFilters {
+        size: args
+            .size
+            .as_deref()
+            .map(parse_size_filter)
+            .transpose()?
+            .unwrap_or_default(),
+        newer_than: args
+            .newer
+            .as_deref()
+            .map(parse_time_threshold)
+            .transpose()?,
+        older_than: args
+            .older
+            .as_deref()
+            .map(parse_time_threshold)
+            .transpose()?,
+    })
+}
+
 /// Run ripgrep‑style search over the working tree.
-fn find_matches(pattern: &str) -> Vec<MatchResult> {
+fn find_matches(pattern: &str, args: &Args) -> Vec<MatchResult> {
     let matcher = RegexMatcher::new(pattern).expect("Invalid regular expression");
-    let root = Path::new(".");
+    let roots = search_roots(args);
+    let overrides = build_overrides(args).expect("Invalid glob pattern");
+    let types = build_types(args).expect("Invalid file type");
+    let file_filters = build_file_filters(args).expect("Invalid --size/--newer/--older filter");
     let matches = Arc::new(Mutex::new(Vec::<MatchResult>::new()));
 
-    WalkBuilder::new(root).build_parallel().run(|| {
+    let mut walk_builder = WalkBuilder::new(&roots[0]);
+    for extra_root in &roots[1..] {
+        walk_builder.add(extra_root);
+    }
+    walk_builder.overrides(overrides);
+    walk_builder.types(types);
+
+    walk_builder.build_parallel().run(|| {
         let matcher = matcher.clone();
         let matches_outer = matches.clone();
 
@@ -87,6 +460,10 @@ fn find_matches(pattern: &str) -> Vec<MatchResult> {
                 let path_for_search = entry.path().to_path_buf();
                 let path_for_vec = entry.path().to_path_buf();
 
+                if !file_filters.matches(&path_for_search) {
+                    return ignore::WalkState::Continue;
+                }
+
                 let matches_inner = matches_outer.clone();
 
                 let mut searcher = SearcherBuilder::new()
@@ -156,6 +533,54 @@ fn sort_matches(mut matches: Vec<MatchResult>) -> Vec<MatchResult> {
     matches
 }
 
+/// Map an `lscolors::Color` onto the closest `termcolor::Color` variant,
+/// plus whether it's a "bright" variant (applied via `ColorSpec::set_intense`,
+/// since `termcolor::Color` has no separate bright variants of its own).
+fn lscolors_color_to_termcolor(color: &lscolors::Color) -> (termcolor::Color, bool) {
+    use lscolors::Color::*;
+    match color {
+        Black => (termcolor::Color::Black, false),
+        Red => (termcolor::Color::Red, false),
+        Green => (termcolor::Color::Green, false),
+        Yellow => (termcolor::Color::Yellow, false),
+        Blue => (termcolor::Color::Blue, false),
+        Magenta => (termcolor::Color::Magenta, false),
+        Cyan => (termcolor::Color::Cyan, false),
+        White => (termcolor::Color::White, false),
+        BrightBlack => (termcolor::Color::Black, true),
+        BrightRed => (termcolor::Color::Red, true),
+        BrightGreen => (termcolor::Color::Green, true),
+        BrightYellow => (termcolor::Color::Yellow, true),
+        BrightBlue => (termcolor::Color::Blue, true),
+        BrightMagenta => (termcolor::Color::Magenta, true),
+        BrightCyan => (termcolor::Color::Cyan, true),
+        BrightWhite => (termcolor::Color::White, true),
+        Fixed(n) => (termcolor::Color::Ansi256(*n), false),
+        RGB(r, g, b) => (termcolor::Color::Rgb(*r, *g, *b), false),
+    }
+}
+
+/// Build the `ColorSpec` `LS_COLORS` assigns to `path`, falling back to the
+/// plain spec when the path has no matching rule.
+fn lscolors_spec_for(ls_colors: &LsColors, path: &Path) -> ColorSpec {
+    let mut spec = ColorSpec::new();
+    if let Some(style) = ls_colors.style_for_path(path) {
+        if let Some(fg) = style.foreground.as_ref() {
+            let (color, intense) = lscolors_color_to_termcolor(fg);
+            spec.set_fg(Some(color));
+            spec.set_intense(intense);
+        }
+        if let Some(bg) = style.background.as_ref() {
+            let (color, _intense) = lscolors_color_to_termcolor(bg);
+            spec.set_bg(Some(color));
+        }
+        spec.set_bold(style.font_style.bold);
+        spec.set_italic(style.font_style.italic);
+        spec.set_underline(style.font_style.underline);
+    }
+    spec
+}
+
 /// Pretty‑print results with optional score column.
 fn print_matches(matches: Vec<MatchResult>, pattern: &str, args: Args) {
     let matcher = RegexMatcher::new(pattern).expect("Invalid regular expression");
@@ -170,6 +595,13 @@ fn print_matches(matches: Vec<MatchResult>, pattern: &str, args: Args) {
     let mut highlight = ColorSpec::new();
     highlight.set_fg(Some(termcolor::Color::Red)).set_bold(true);
 
+    let ls_colors = (!args.no_filename_color && args.color != Color::Never).then(|| {
+        std::env::var("LS_COLORS")
+            .ok()
+            .map(|s| LsColors::from_string(&s))
+            .unwrap_or_default()
+    });
+
     for m in matches {
         if let Ok(Some(matched)) = matcher.find(m.line_text.as_bytes()) {
             let (start, end) = (matched.start(), matched.end());
@@ -180,7 +612,14 @@ fn print_matches(matches: Vec<MatchResult>, pattern: &str, args: Args) {
                 write!(stdout, "{:.2}: ", m.frecency_score * 1e8).unwrap();
             }
 
-            write!(stdout, "{}:{}:", m.path.display(), m.line_number).unwrap();
+            if let Some(lsc) = &ls_colors {
+                stdout.set_color(&lscolors_spec_for(lsc, &m.path)).unwrap();
+            }
+            write!(stdout, "{}", m.path.display()).unwrap();
+            if ls_colors.is_some() {
+                stdout.set_color(&normal).unwrap();
+            }
+            write!(stdout, ":{}:", m.line_number).unwrap();
 
             if args.column {
                 write!(stdout, "{}:", start + 1).unwrap();
@@ -199,22 +638,70 @@ fn print_matches(matches: Vec<MatchResult>, pattern: &str, args: Args) {
     }
 }
 
+/// Print the available `--format` verb templates and where each was loaded
+/// from, one per line.
+fn list_verbs() -> Result<()> {
+    let manager = VerbManager::new()?;
+    for template in manager.list_templates() {
+        println!("{} ({:?})", template.name, template.source);
+    }
+    Ok(())
+}
+
+/// Render each match through the `--format` verb template `name` instead of
+/// the default layout, one rendered line per match.
+fn print_matches_with_verb(matches: Vec<MatchResult>, pattern: &str, name: &str) -> Result<()> {
+    let matcher = RegexMatcher::new(pattern).expect("Invalid regular expression");
+    let manager = VerbManager::new()?;
+    let mut stdout = std::io::stdout();
+
+    for m in matches {
+        if let Ok(Some(matched)) = matcher.find(m.line_text.as_bytes()) {
+            let line_text = m.line_text.trim_end_matches(&['\r', '\n'][..]);
+            let context = serde_json::json!({
+                "path": m.path.display().to_string(),
+                "line_number": m.line_number,
+                "line_text": line_text,
+                "column": matched.start() + 1,
+                "frecency_score": m.frecency_score,
+            });
+
+            let rendered = manager.render_template(name, &context)?;
+            writeln!(stdout, "{rendered}")?;
+        }
+    }
+
+    Ok(())
+}
+
 fn main() -> Result<()> {
     let args = Args::parse();
+
+    if args.type_list {
+        return print_type_list();
+    }
+    if args.list_verbs {
+        return list_verbs();
+    }
+
     let case_insensitive =
-        args.ignore_case || (args.smart_case && args.pattern.to_lowercase() == args.pattern);
+        args.ignore_case || (args.smart_case && !pattern_has_uppercase_char(&args.pattern));
     let pattern_str = if case_insensitive {
         format!("(?i){}", args.pattern)
     } else {
         args.pattern.clone()
     };
 
-    let mut matches = find_matches(&pattern_str);
+    let mut matches = find_matches(&pattern_str, &args);
 
     calculate_frecencies(&mut matches)?;
 
     let sorted_matches = sort_matches(matches);
-    print_matches(sorted_matches, &pattern_str, args);
+
+    match &args.format {
+        Some(name) => print_matches_with_verb(sorted_matches, &pattern_str, name)?,
+        None => print_matches(sorted_matches, &pattern_str, args),
+    }
 
     Ok(())
 }
@@ -416,4 +903,219 @@ mod tests {
         assert!(stdout.contains("case.rs:2"), "Expected match for 'hello'");
         assert!(stdout.contains("case.rs:3"), "Expected match for 'HELLO'");
     }
+
+    #[test]
+    fn path_argument_restricts_search_to_subtree() {
+        let dir = create_mock_repo(&[("src/keep.rs", 1), ("other/skip.rs", 1)]);
+
+        let stdout = run_zg(&dir, &["println!", "src"]);
+
+        assert!(stdout.contains("keep.rs"), "Expected match in src/");
+        assert!(
+            !stdout.contains("skip.rs"),
+            "Unexpected match outside requested path"
+        );
+    }
+
+    #[test]
+    fn glob_excludes_matching_files() {
+        let dir = create_mock_repo(&[("keep.rs", 1), ("skip.txt", 1)]);
+        std::fs::write(dir.path().join("skip.txt"), "println!(\"txt\");\n").unwrap();
+
+        let stdout = run_zg(&dir, &["println!", "--glob", "!*.txt"]);
+
+        assert!(stdout.contains("keep.rs"), "Expected match for keep.rs");
+        assert!(!stdout.contains("skip.txt"), "Expected skip.txt excluded");
+    }
+
+    #[test]
+    fn include_glob_with_literal_prefix_still_matches_narrowed_root() {
+        let dir = create_mock_repo(&[("src/lib.rs", 1), ("other/skip.rs", 1)]);
+
+        let stdout = run_zg(&dir, &["println!", "-g", "src/**/*.rs"]);
+
+        assert!(stdout.contains("lib.rs"), "Expected match for src/lib.rs");
+        assert!(
+            !stdout.contains("skip.rs"),
+            "Unexpected match outside src/"
+        );
+    }
+
+    #[test]
+    fn multiple_include_globs_with_diverging_prefixes_walk_both_trees() {
+        let dir = create_mock_repo(&[
+            ("src/lib.rs", 1),
+            ("tests/it.rs", 1),
+            ("other/skip.rs", 1),
+        ]);
+
+        let stdout = run_zg(
+            &dir,
+            &["println!", "-g", "src/**/*.rs", "-g", "tests/**/*.rs"],
+        );
+
+        assert!(stdout.contains("lib.rs"), "Expected match for src/lib.rs");
+        assert!(stdout.contains("it.rs"), "Expected match for tests/it.rs");
+        assert!(
+            !stdout.contains("skip.rs"),
+            "Unexpected match outside src/ and tests/"
+        );
+    }
+
+    #[test]
+    fn type_filter_restricts_to_selected_language() {
+        let dir = create_mock_repo(&[("keep.rs", 1), ("skip.py", 1)]);
+        std::fs::write(dir.path().join("skip.py"), "print('println!')\n").unwrap();
+
+        let stdout = run_zg(&dir, &["println!", "-t", "rust"]);
+
+        assert!(stdout.contains("keep.rs"), "Expected match for keep.rs");
+        assert!(!stdout.contains("skip.py"), "Expected skip.py excluded");
+    }
+
+    #[test]
+    fn smart_case_ignores_uppercase_inside_escape_tokens() {
+        let dir = create_mock_repo(&[("case.rs", 1)]);
+        let file_path = dir.path().join("case.rs");
+        std::fs::write(&file_path, "hello world\nHELLO WORLD\n").unwrap();
+
+        let stdout = run_zg(&dir, &["-S", r"w\S+ld"]);
+
+        assert!(stdout.contains("case.rs:1"), "Expected match for lowercase");
+        assert!(
+            stdout.contains("case.rs:2"),
+            "Expected the \\S escape's uppercase letter to be ignored, keeping the match case-insensitive"
+        );
+    }
+
+    #[test]
+    fn pattern_has_uppercase_char_ignores_fixed_width_unicode_escapes() {
+        assert!(
+            !super::pattern_has_uppercase_char("\\u00C0hello"),
+            "\\u escape's 4 hex digits should be skipped, not scanned for uppercase"
+        );
+        assert!(
+            !super::pattern_has_uppercase_char(r"\U0010FFFFhello"),
+            "\\U escape's 8 hex digits should be skipped, not scanned for uppercase"
+        );
+        assert!(super::pattern_has_uppercase_char("Hello"));
+    }
+
+    #[test]
+    fn no_filename_color_still_prints_matches() {
+        let dir = create_mock_repo(&[("main.rs", 1)]);
+        std::fs::write(
+            dir.path().join("main.rs"),
+            "fn main() {\n    println!(\"main\");\n}\n",
+        )
+        .unwrap();
+
+        let stdout = run_zg(&dir, &["println!", "--color=always", "--no-filename-color"]);
+
+        assert!(
+            stdout.contains("main.rs:2"),
+            "Expected match for main.rs, got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn type_list_flag_prints_known_types() {
+        let dir = create_mock_repo(&[("keep.rs", 1)]);
+        let stdout = run_zg(&dir, &["--type-list"]);
+
+        assert!(
+            stdout.lines().any(|l| l.starts_with("rust:")),
+            "Expected a 'rust:' entry, got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn size_filter_excludes_smaller_files() {
+        let dir = create_mock_repo(&[("small.rs", 1), ("big.rs", 1)]);
+        std::fs::write(dir.path().join("small.rs"), "println!(\"hi\");\n").unwrap();
+        std::fs::write(
+            dir.path().join("big.rs"),
+            format!("// {}\nprintln!(\"hi\");\n", "x".repeat(200)),
+        )
+        .unwrap();
+
+        let stdout = run_zg(&dir, &["println!", "--size", "+100"]);
+
+        assert!(stdout.contains("big.rs"), "Expected match for big.rs");
+        assert!(!stdout.contains("small.rs"), "Expected small.rs excluded");
+    }
+
+    #[test]
+    fn newer_filter_with_distant_past_includes_everything() {
+        let dir = create_mock_repo(&[("main.rs", 1)]);
+
+        let stdout = run_zg(&dir, &["println!", "--newer", "100years"]);
+
+        assert!(stdout.contains("main.rs"), "Expected match for main.rs");
+    }
+
+    #[test]
+    fn older_filter_with_distant_past_excludes_everything() {
+        let dir = create_mock_repo(&[("main.rs", 1)]);
+
+        let stdout = run_zg(&dir, &["println!", "--older", "100years"]);
+
+        assert!(
+            !stdout.contains("main.rs"),
+            "Expected no matches, everything is newer than 100 years ago"
+        );
+    }
+
+    #[test]
+    fn newer_filter_accepts_bare_date() {
+        let dir = create_mock_repo(&[("main.rs", 1)]);
+
+        let stdout = run_zg(&dir, &["println!", "--newer", "2024-01-01"]);
+
+        assert!(
+            stdout.contains("main.rs"),
+            "Expected a bare date like '2024-01-01' to be accepted, got:\n{stdout}"
+        );
+    }
+
+    #[test]
+    fn format_flag_renders_custom_verb_template() {
+        let dir = create_mock_repo(&[("main.rs", 1)]);
+        std::fs::write(
+            dir.path().join("main.rs"),
+            "fn main() {\n    println!(\"main\");\n}\n",
+        )
+        .unwrap();
+
+        let verbs_dir = dir.path().join(".lakonik/verbs");
+        std::fs::create_dir_all(&verbs_dir).unwrap();
+        std::fs::write(
+            verbs_dir.join("editor.jinja"),
+            "edit +{{ line_number }} {{ path }}",
+        )
+        .unwrap();
+
+        let stdout = run_zg(&dir, &["println!", "--format", "editor"]);
+
+        assert!(
+            stdout.contains("edit +2"),
+            "Expected rendered verb output, got:\n{stdout}"
+        );
+        assert!(stdout.contains("main.rs"));
+    }
+
+    #[test]
+    fn list_verbs_flag_shows_local_template() {
+        let dir = create_mock_repo(&[("main.rs", 1)]);
+        let verbs_dir = dir.path().join(".lakonik/verbs");
+        std::fs::create_dir_all(&verbs_dir).unwrap();
+        std::fs::write(verbs_dir.join("editor.jinja"), "edit {{ path }}").unwrap();
+
+        let stdout = run_zg(&dir, &["--list-verbs"]);
+
+        assert!(
+            stdout.contains("editor"),
+            "Expected 'editor' verb listed, got:\n{stdout}"
+        );
+    }
 }
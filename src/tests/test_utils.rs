@@ -15,6 +15,9 @@ pub fn create_mock_repo(spec: &[(&str, usize)]) -> TempDir {
         for n in 0..*commit_count {
             // overwrite the file
             {
+                if let Some(parent) = file_path.parent() {
+                    std::fs::create_dir_all(parent).unwrap();
+                }
                 let mut f = File::create(&file_path).unwrap();
                 writeln!(f, "fn f_{n}() {{ println!(\"{file_name} #{n}\"); }}").unwrap();
             }
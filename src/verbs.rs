@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use minijinja::{Environment, Template};
+use minijinja::Environment;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
@@ -33,7 +33,7 @@ impl VerbManager {
         Self::load_local_templates(&mut templates)?;
 
         for template in &templates {
-            env.add_template(&template.name, &template.content)
+            env.add_template_owned(template.name.clone(), template.content.clone())
                 .with_context(|| format!("Failed to add template: {}", template.name))?;
         }
 
@@ -113,25 +113,22 @@ impl VerbManager {
             templates.push(VerbTemplate {
                 name: name.to_string(),
                 content,
-                source,
+                source: source.clone(),
             });
         }
 
         Ok(())
     }
 
-    pub fn get_template(&self, name: &str) -> Option<&Template> {
-        self.env.get_template(name)
-    }
-
     pub fn list_templates(&self) -> &[VerbTemplate] {
         &self.templates
     }
 
     pub fn render_template(&self, name: &str, context: &serde_json::Value) -> Result<String> {
         let template = self
+            .env
             .get_template(name)
-            .ok_or_else(|| anyhow::anyhow!("Template not found: {}", name))?;
+            .map_err(|_| anyhow::anyhow!("Template not found: {}", name))?;
 
         template
             .render(context)